@@ -50,7 +50,11 @@ struct Args {
     
     /// Tape size for the brainfuck interpreter (when using --run)
     #[arg(short = 't', long, default_value = "150000000")]
-    tape_size: usize
+    tape_size: usize,
+    
+    /// Disable colored output from the invoked tools (also honored via NO_COLOR)
+    #[arg(long = "no-color", default_value_t = false)]
+    no_color: bool,
 }
 
 struct BuildContext {
@@ -86,6 +90,15 @@ impl BuildContext {
         which(name).with_context(|| format!("Could not find '{}' in PATH", name))
     }
 
+    /// Create a command for a toolchain binary, forwarding --no-color as NO_COLOR
+    fn tool_command(&self, tool: PathBuf) -> Command {
+        let mut cmd = Command::new(tool);
+        if self.args.no_color {
+            cmd.env("NO_COLOR", "1");
+        }
+        cmd
+    }
+
     fn run_command(&self, cmd: &mut Command) -> Result<()> {
         if self.args.verbose {
             eprintln!("Running: {:?}", cmd);
@@ -115,7 +128,7 @@ impl BuildContext {
         
         let output = self.get_temp_path(&format!("{}.pobj", stem));
         
-        let mut cmd = Command::new(rasm);
+        let mut cmd = self.tool_command(rasm);
         cmd.arg("assemble")
             .arg("-b")
             .arg(self.args.bank_size.to_string())
@@ -138,7 +151,7 @@ impl BuildContext {
             self.get_temp_path("linked.bin")
         };
 
-        let mut cmd = Command::new(rlink);
+        let mut cmd = self.tool_command(rlink);
         
         for obj in &self.object_files {
             cmd.arg(obj);
@@ -177,7 +190,7 @@ impl BuildContext {
             PathBuf::from(format!("{}.bf", stem))
         };
 
-        let mut cmd = Command::new(bfm);
+        let mut cmd = self.tool_command(bfm);
         cmd.arg("expand")
             .arg(macro_file)
             .arg("-o")
@@ -192,7 +205,7 @@ impl BuildContext {
     fn expand_to_stdout(&self, macro_file: &Path) -> Result<String> {
         let bfm = Self::check_tool("bfm")?;
         
-        let mut cmd = Command::new(bfm);
+        let mut cmd = self.tool_command(bfm);
         cmd.arg("expand")
             .arg(macro_file);
 
@@ -220,7 +233,7 @@ impl BuildContext {
         use std::io::Write;
         use std::process::Stdio;
         
-        let mut cmd = Command::new(bf)
+        let mut cmd = self.tool_command(bf)
             .arg("--tape-size")
             .arg(self.args.tape_size.to_string())
             .arg("--cell-size")
@@ -331,4 +344,36 @@ fn main() -> Result<()> {
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(extra: &[&str]) -> BuildContext {
+        let mut argv = vec!["rbt"];
+        argv.extend_from_slice(extra);
+        argv.push("prog.asm");
+        BuildContext::new(Args::parse_from(argv)).unwrap()
+    }
+
+    fn no_color_env(cmd: &Command) -> Option<String> {
+        cmd.get_envs()
+            .find(|(key, _)| *key == "NO_COLOR")
+            .and_then(|(_, value)| value.map(|v| v.to_string_lossy().into_owned()))
+    }
+
+    #[test]
+    fn no_color_flag_sets_no_color_for_tools() {
+        let ctx = context(&["--no-color"]);
+        let cmd = ctx.tool_command(PathBuf::from("rasm"));
+        assert_eq!(no_color_env(&cmd).as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn tools_inherit_environment_without_flag() {
+        let ctx = context(&[]);
+        let cmd = ctx.tool_command(PathBuf::from("rasm"));
+        assert_eq!(no_color_env(&cmd), None);
+    }
+}
@@ -12,7 +12,8 @@ use std::path::PathBuf;
 )]
 pub struct Cli {
     /// Binary file to execute
-    pub binary_file: PathBuf,
+    #[arg(required_unless_present = "dump_rng")]
+    pub binary_file: Option<PathBuf>,
     
     /// Set bank size
     #[arg(short = 'b', long, default_value = "65535")]
@@ -30,6 +31,10 @@ pub struct Cli {
     #[arg(short = 's', long)]
     pub seed: Option<String>,
     
+    /// Print the first N RNG values for the seed and exit without running
+    #[arg(long, value_name = "N")]
+    pub dump_rng: Option<usize>,
+    
     /// Pre-populate input buffer with text
     #[arg(short = 'i', long)]
     pub input: Option<String>,
@@ -50,12 +55,34 @@ pub struct Cli {
     #[arg(long)]
     pub visual: bool,
     
+    /// Stop (or pause in debug mode) when ADD/SUB/MUL overflow the signed 16-bit range.
+    /// ADDI/MULI immediates are read as signed too, so an immediate >= 0x8000 is negative
+    #[arg(long)]
+    pub trap_overflow: bool,
+    
+    /// How program output bytes are written to stdout
+    #[arg(long, value_enum, default_value = "raw")]
+    pub output_mode: crate::vm::OutputMode,
+    
     /// Path to disk image file for storage (default: ~/.RippleVM/disk.img)
     #[arg(long)]
     pub disk: Option<PathBuf>,
+    
+    /// Disable colored output (also honored via the NO_COLOR environment variable)
+    #[arg(long)]
+    pub no_color: bool,
+    
+    /// Print a JSON run result (output, registers, instruction count) instead of program output
+    #[arg(long, conflicts_with_all = ["debug", "tui", "visual"])]
+    pub json_output: bool,
 }
 
 impl Cli {
+    /// Whether colored output should be disabled (--no-color or a non-empty NO_COLOR)
+    pub fn color_disabled(&self) -> bool {
+        no_color_requested(self.no_color, std::env::var_os("NO_COLOR").as_deref())
+    }
+    
     /// Parse RNG seed from string (hex or decimal)
     pub fn parse_seed(&self) -> Option<u32> {
         self.seed.as_ref().map(|s| {
@@ -84,6 +111,11 @@ impl Cli {
     }
 }
 
+/// Decide on color from the flag and the value of NO_COLOR, which only counts when non-empty
+fn no_color_requested(no_color_flag: bool, no_color_env: Option<&std::ffi::OsStr>) -> bool {
+    no_color_flag || no_color_env.is_some_and(|v| !v.is_empty())
+}
+
 /// Parse frequency from string (e.g., "1MHz", "500KHz", "1000000", "2.5MHz")
 pub fn parse_frequency(s: &str) -> Result<u64, String> {
     let s = s.trim();
@@ -112,4 +144,37 @@ fn parse_float_with_multiplier(s: &str, multiplier: u64) -> Result<u64, String>
     } else {
         Err(format!("Invalid numeric value: {s}"))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+
+    #[test]
+    fn no_color_flag_disables_color() {
+        let cli = Cli::parse_from(["rvm", "--no-color", "prog.bin"]);
+        assert!(cli.no_color);
+        assert!(no_color_requested(cli.no_color, None));
+    }
+
+    #[test]
+    fn empty_no_color_is_ignored() {
+        assert!(!no_color_requested(false, None));
+        assert!(!no_color_requested(false, Some(OsStr::new(""))));
+    }
+
+    #[test]
+    fn non_empty_no_color_disables_color() {
+        assert!(no_color_requested(false, Some(OsStr::new("1"))));
+    }
+
+    #[test]
+    fn color_override_strips_ansi_escapes() {
+        use colored::Colorize;
+
+        // main() applies this override when color_disabled() is true
+        colored::control::set_override(false);
+        assert!(!"x".red().to_string().contains('\x1b'));
+    }
+}
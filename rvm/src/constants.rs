@@ -41,7 +41,17 @@ pub const STORE_DIRTY: u16 = 1 << 1;       // bit1: Current block has uncommitte
 pub const STORE_COMMIT: u16 = 1 << 2;      // bit2: Trigger commit of current block
 pub const STORE_COMMIT_ALL: u16 = 1 << 3;  // bit3: Trigger commit of all dirty blocks
 
-// Words 21..31 are reserved for future use
+// RGB565 framebuffer location (bank 0, word 21)
+pub const HDR_FB_BASE: usize = 21; // Read: first word of the RGB565 front buffer (bank 0), 0 outside RGB565 mode
+
+// Words 22..31 are reserved for future use (see VM::register_mmio_device)
+
+// RNG: 32-bit LCG (Numerical Recipes parameters), state = state * A + C mod 2^32,
+// HDR_RNG returns the high 16 bits. Part of the VM contract - changing these
+// breaks reproducibility of seeded runs.
+pub const RNG_DEFAULT_SEED: u32 = 0x12345678;
+pub const RNG_MULTIPLIER: u32 = 1664525;
+pub const RNG_INCREMENT: u32 = 1013904223;
 
 // TEXT40 display constants
 pub const TEXT40_BASE_WORD: usize  = 32;         // Start of VRAM
@@ -200,6 +200,7 @@ impl Debugger {
             crate::vm::VMState::Running => "Running".bright_green(),
             crate::vm::VMState::Halted => "Halted".bright_red(),
             crate::vm::VMState::Breakpoint => "Breakpoint".bright_yellow().bold(),
+            crate::vm::VMState::Trap => "Trap".bright_magenta().bold(),
             crate::vm::VMState::Error(e) => format!("Error: {e}").bright_red().bold(),
         }
     }
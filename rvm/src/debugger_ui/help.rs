@@ -21,6 +21,8 @@ impl TuiDebugger {
             Line::from(Span::styled("── Execution ──", Style::default().fg(Color::Yellow))),
             Line::from("Space/s  Step | r  Run | c  Continue"),
             Line::from("R  Restart | b  Breakpoint"),
+            Line::from("Yellow regs changed on last step"),
+            Line::from("Prev: shows their old values"),
             Line::from(""),
             Line::from(Span::styled("── Disassembly ──", Style::default().fg(Color::Yellow))),
             Line::from("Shift+H  Hex view | 0-9,a-f  Edit"),
@@ -39,6 +41,7 @@ impl TuiDebugger {
             Line::from(Span::styled("── Commands (:) ──", Style::default().fg(Color::Yellow))),
             Line::from(":break <a> | :mem <a> <v>"),
             Line::from(":bank <n> | :reg <#> <v>"),
+            Line::from(":run-to <a>  Run until PC = a"),
             Line::from(":q  Quit"),
             Line::from(""),
             Line::from(Span::styled("── Edit Formats ──", Style::default().fg(Color::Yellow))),
@@ -4,7 +4,7 @@ use ratatui::prelude::{Color, Line, Modifier, Span, Style};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ripple_asm::Register;
 use crate::tui_debugger::{FocusedPane, TuiDebugger};
-use crate::vm::VM;
+use crate::vm::{VM, MemoryRegion};
 
 impl TuiDebugger {
     pub(crate) fn draw_memory(&self, frame: &mut Frame, area: Rect, vm: &VM) {
@@ -21,11 +21,10 @@ impl TuiDebugger {
         let cursor_addr = self.memory_base_addr + self.memory_scroll * bytes_per_row + self.memory_cursor_col;
         
         // Get stack information
-        let sp = vm.registers[Register::Sp as usize];
-        let sb = vm.registers[Register::Sb as usize];
         let fp = vm.registers[Register::Fp as usize];
-        let stack_base_addr = sb as usize * vm.bank_size as usize;
-        let stack_top_addr = stack_base_addr + sp as usize;
+        let stack = vm.stack_range();
+        let stack_base_addr = stack.start;
+        let stack_top_addr = stack.end;
         let frame_addr = stack_base_addr + fp as usize;
 
         for row in 0..visible_rows {
@@ -55,6 +54,13 @@ impl TuiDebugger {
                     // Don't break - SP or FP might be in same row
                 }
             }
+            
+            // Otherwise label the row with the region its first address belongs to
+            if row_indicator == "    " {
+                let region = vm.memory_region(addr);
+                row_indicator = region.label();
+                indicator_color = Self::region_color(region);
+            }
 
             let mut spans = vec![
                 Span::styled(row_indicator, Style::default().fg(indicator_color)),
@@ -95,7 +101,10 @@ impl TuiDebugger {
                         // Special I/O registers
                         Style::default().fg(Color::Magenta)
                     } else if value != 0 {
-                        Style::default().fg(Color::White)
+                        match vm.memory_region(idx) {
+                            MemoryRegion::Unmapped => Style::default().fg(Color::White),
+                            region => Style::default().fg(Self::region_color(region)),
+                        }
                     } else {
                         Style::default().fg(Color::DarkGray)
                     };
@@ -170,4 +179,14 @@ impl TuiDebugger {
         frame.render_widget(paragraph, area);
     }
 
+    fn region_color(region: MemoryRegion) -> Color {
+        match region {
+            MemoryRegion::Mmio => Color::Magenta,
+            MemoryRegion::Vram => Color::LightBlue,
+            MemoryRegion::Stack => Color::Yellow,
+            MemoryRegion::Data => Color::LightGreen,
+            MemoryRegion::Heap => Color::Cyan,
+            MemoryRegion::Unmapped => Color::DarkGray,
+        }
+    }
 }
\ No newline at end of file
@@ -8,4 +8,6 @@ mod output;
 mod breakpoints;
 mod status_line;
 mod input_line;
-mod help;
\ No newline at end of file
+mod help;
+
+pub(crate) use registers::changed_registers;
\ No newline at end of file
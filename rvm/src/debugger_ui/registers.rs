@@ -28,8 +28,8 @@ impl TuiDebugger {
         spans.push(Span::styled("0000", Style::default().fg(Color::DarkGray)));
         text.push(Line::from(spans));
         
-        // Empty line after hardware registers
-        text.push(Line::from(""));
+        // Previous values of registers changed by the last step
+        text.push(self.format_register_diff());
 
         // Second row: Return values (RV0-RV1)
         let mut spans = Vec::new();
@@ -131,6 +131,31 @@ impl TuiDebugger {
         frame.render_widget(paragraph, area);
     }
 
+    fn format_register_diff(&self) -> Line<'_> {
+        let mut changed: Vec<usize> = self.register_changes.keys()
+            .copied()
+            .filter(|&i| i != Register::Pc as usize && i != Register::Pcb as usize)
+            .collect();
+        changed.sort_unstable();
+        
+        if changed.is_empty() {
+            return Line::from("");
+        }
+        
+        let mut spans = vec![Span::styled("Prev:", Style::default().fg(Color::DarkGray))];
+        for i in changed {
+            let name = Register::from_u8(i as u8).map_or("??", |r| r.to_str());
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(name, Style::default().fg(Color::Gray)));
+            spans.push(Span::styled(":", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(
+                format!("{:04X}", self.register_changes[&i]),
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::DIM)
+            ));
+        }
+        Line::from(spans)
+    }
+
     #[allow(dead_code)]
     fn format_register(&self, name: &str, index: usize, vm: &VM) -> Vec<Span<'_>> {
         let value = vm.registers[index];
@@ -179,4 +204,37 @@ impl TuiDebugger {
             Span::styled(format!("{value:04X}"), value_style),
         ]
     }
-}
\ No newline at end of file
+}
+
+/// Indices of the registers whose values differ between two snapshots
+pub(crate) fn changed_registers(old: &[u16; 32], new: &[u16; 32]) -> Vec<usize> {
+    (0..old.len()).filter(|&i| old[i] != new[i]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::changed_registers;
+
+    #[test]
+    fn no_change_reports_nothing() {
+        let regs = [7u16; 32];
+        assert!(changed_registers(&regs, &regs).is_empty());
+    }
+
+    #[test]
+    fn single_change_is_reported() {
+        let old = [0u16; 32];
+        let mut new = old;
+        new[5] = 0x1234;
+        assert_eq!(changed_registers(&old, &new), vec![5]);
+    }
+
+    #[test]
+    fn first_and_last_registers_are_reported_in_order() {
+        let old = [0u16; 32];
+        let mut new = old;
+        new[31] = 1;
+        new[0] = 1;
+        assert_eq!(changed_registers(&old, &new), vec![0, 31]);
+    }
+}
@@ -14,6 +14,7 @@ impl TuiDebugger {
             VMState::Running => Color::Green,
             VMState::Halted => Color::Red,
             VMState::Breakpoint => Color::Yellow,
+            VMState::Trap => Color::Magenta,
             VMState::Error(_) => Color::Red,
             VMState::Setup => Color::Gray,
         };
@@ -23,14 +24,15 @@ impl TuiDebugger {
                 VMState::Running => "RUNNING",
                 VMState::Halted => "HALTED",
                 VMState::Breakpoint => "BREAKPOINT",
+                VMState::Trap => "TRAP",
                 VMState::Error(_) => "ERROR",
                 VMState::Setup => "SETUP",
             }),
             Style::default().bg(state_color).fg(Color::Black).add_modifier(Modifier::BOLD)
         ));
 
-        // Show status message if present
-        if let Some(ref msg) = self.status_message {
+        // Show status message if present (a VM trap takes precedence)
+        if let Some(ref msg) = vm.trap_message.as_ref().or(self.status_message.as_ref()) {
             spans.push(Span::raw(" "));
             spans.push(Span::styled(
                 format!(" {msg} "),
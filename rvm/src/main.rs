@@ -7,20 +7,36 @@ mod mode;
 mod settings;
 mod display_rgb565;
 mod cli;
+mod run_result;
 
 use std::fs;
 use std::io::{self, Write};
 use std::process;
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::Instant;
 use vm::VM;
-use constants::DEFAULT_MEMORY_SIZE;
+use constants::{DEFAULT_MEMORY_SIZE, RNG_DEFAULT_SEED};
 use debug::Debugger;
 use colored::*;
 use crossterm::{terminal, cursor, style::ResetColor, ExecutableCommand};
 use clap::Parser;
 use cli::Cli;
+use run_result::RunResult;
+
+/// Exit status when BRK stops a program outside the debugger: 128 + SIGABRT(6), like a failed C assert
+const TRAP_EXIT_CODE: i32 = 134;
+
+/// Print where a trapped program stopped; returns whether the VM is in the trap state
+fn report_trap(vm: &VM) -> bool {
+    if !matches!(vm.state, vm::VMState::Trap) {
+        return false;
+    }
+    if let Some(ref message) = vm.trap_message {
+        eprintln!("Trap: {message}");
+    }
+    true
+}
 
 /// Install signal handlers to ensure terminal cleanup on exit
 fn install_signal_handlers() {
@@ -55,6 +71,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Parse command line arguments
     let cli = Cli::parse();
     
+    if cli.color_disabled() {
+        colored::control::set_override(false);
+    }
+    
     let bank_size = cli.bank_size;
     let memory_size = cli.memory;
     let frequency = cli.parse_frequency();
@@ -62,10 +82,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let input_text = cli.input.clone();
     let debug_mode = cli.debug;
     let tui_mode = cli.tui;
-    let verbose = cli.verbose;
+    let json_output = cli.json_output;
+    let verbose = cli.verbose && !json_output;
     let visual_mode = cli.visual;
     let disk_path = cli.disk.clone();
-    let file_path = cli.binary_file;
+    
+    // Dump the RNG sequence for the seed without loading or running anything
+    if let Some(count) = cli.dump_rng {
+        let mut state = rng_seed.unwrap_or(RNG_DEFAULT_SEED);
+        for _ in 0..count {
+            println!("0x{:04X}", VM::rng_step(&mut state));
+        }
+        return Ok(());
+    }
+    
+    let file_path = cli.binary_file.expect("binary file is required unless --dump-rng is given");
     
     // Read the binary file
     let binary = fs::read(&file_path).unwrap_or_else(|e| {
@@ -90,6 +121,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // Set verbose mode if requested
     vm.verbose = verbose;
+    vm.trap_overflow = cli.trap_overflow;
+    vm.output_mode = cli.output_mode;
     
     // Pre-populate input buffer if provided
     if let Some(input) = input_text {
@@ -148,7 +181,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             
             // Run with frequency limiting if specified
             if let Some(freq) = frequency_clone {
-                if let Err(e) = run_with_frequency(&mut vm, freq) {
+                if let Err(e) = vm.run_with_frequency(freq) {
                     eprintln!("Runtime error: {}", e);
                 }
             } else {
@@ -157,6 +190,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
             
+            report_trap(&vm);
             eprintln!("VM execution completed. Close the window to exit.");
             // Don't shutdown display immediately - let user close the window
         });
@@ -168,6 +202,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         
         // Wait for VM thread to finish
         let _ = vm_thread.join();
+        if vm.lock().is_ok_and(|vm| matches!(vm.state, vm::VMState::Trap)) {
+            process::exit(TRAP_EXIT_CODE);
+        }
         true // VM was moved to Arc
     } else if tui_mode {
         // Use the TUI debugger_ui
@@ -199,6 +236,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
                 vm::VMState::Breakpoint => {
                     println!("\n{}", ">>> Breakpoint hit <<<".bright_yellow().bold());
+                    if let Some(ref message) = vm.trap_message {
+                        println!("{}", message.bright_red());
+                    }
                     println!("{}", "Press 'c' to continue, Enter to step".bright_black());
                 }
                 _ => {}
@@ -247,17 +287,40 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
         }
         false // VM not moved to Arc
+    } else if json_output {
+        // Collect output instead of echoing it, then report the run as JSON
+        vm.echo_output = false;
+        let start = Instant::now();
+        let result = if let Some(freq) = frequency {
+            vm.run_with_frequency(freq)
+        } else {
+            vm.run()
+        };
+        
+        let run_result = RunResult::from_vm(&mut vm, start.elapsed(), result.err());
+        println!("{}", serde_json::to_string_pretty(&run_result)?);
+        if run_result.error.is_some() {
+            process::exit(1);
+        }
+        if matches!(vm.state, vm::VMState::Trap) {
+            process::exit(TRAP_EXIT_CODE);
+        }
+        false // VM not moved to Arc
     } else {
         // Run normally with optional frequency limiting
         if let Some(freq) = frequency {
             if verbose {
                 println!("Running at {freq} Hz");
             }
-            run_with_frequency(&mut vm, freq)?;
+            vm.run_with_frequency(freq)?;
         } else if let Err(e) = vm.run() {
             eprintln!("Runtime error: {e}");
             process::exit(1);
         }
+        
+        if report_trap(&vm) {
+            process::exit(TRAP_EXIT_CODE);
+        }
         false // VM not moved to Arc
     };
     
@@ -275,46 +338,5 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _ = io::stderr().execute(ResetColor);
     let _ = io::stderr().flush();
     
-    Ok(())
-}
-
-
-/// Run VM with frequency limiting
-fn run_with_frequency(vm: &mut VM, frequency: u64) -> Result<(), String> {
-    // Target 60 FPS for smooth animation
-    const TARGET_FPS: u64 = 60;
-    const NANOS_PER_SECOND: u64 = 1_000_000_000;
-    
-    // Calculate instructions per frame
-    let instructions_per_frame = frequency / TARGET_FPS;
-    let frame_duration = Duration::from_nanos(NANOS_PER_SECOND / TARGET_FPS);
-    
-    let mut last_frame_time = Instant::now();
-    let mut instructions_in_frame = 0;
-    
-    while matches!(vm.state, vm::VMState::Running) {
-        // Execute one instruction
-        vm.step()?;
-        instructions_in_frame += 1;
-        
-        // Check if we've executed enough instructions for this frame
-        if instructions_in_frame >= instructions_per_frame {
-            // Wait for the rest of the frame duration
-            let elapsed = last_frame_time.elapsed();
-            if elapsed < frame_duration {
-                std::thread::sleep(frame_duration - elapsed);
-            }
-            
-            // Reset for next frame
-            last_frame_time = Instant::now();
-            instructions_in_frame = 0;
-        }
-        
-        // Stop if we hit a breakpoint in debug mode
-        if matches!(vm.state, vm::VMState::Breakpoint) {
-            break;
-        }
-    }
-    
     Ok(())
 }
\ No newline at end of file
@@ -134,6 +134,13 @@ impl TuiDebugger {
                     }
                 }
             }
+            "run-to" | "until" => {
+                // Usage: run-to <addr> - Run until PC reaches address
+                match parts.get(1).and_then(|a| usize::from_str_radix(a.trim_start_matches("0x"), 16).ok()) {
+                    Some(addr) => self.run_to(vm, addr),
+                    None => self.status_message = Some("Usage: run-to <addr> (hex)".to_string()),
+                }
+            }
 
             // Watch commands
             "w" | "watch" => {
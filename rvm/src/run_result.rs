@@ -0,0 +1,92 @@
+use std::time::Duration;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::Serialize;
+use crate::vm::{VM, VMState};
+
+/// Machine-readable summary of a completed run, printed by `--json-output`
+#[derive(Debug, Serialize)]
+pub struct RunResult {
+    /// Bytes written to TTY_OUT, base64 encoded
+    pub output: String,
+    /// Final values of R0-R31
+    pub registers: Vec<u16>,
+    pub instructions_executed: u64,
+    /// One of "halt", "trap", "breakpoint", "error", "running"
+    pub halt_reason: &'static str,
+    pub error: Option<String>,
+    pub runtime_ms: f64,
+}
+
+impl RunResult {
+    pub fn from_vm(vm: &mut VM, runtime: Duration, error: Option<String>) -> Self {
+        let error = error.or_else(|| match vm.state {
+            VMState::Error(ref e) => Some(e.clone()),
+            _ => None,
+        });
+        
+        let halt_reason = if error.is_some() {
+            "error"
+        } else {
+            match vm.state {
+                VMState::Halted => "halt",
+                VMState::Breakpoint => "breakpoint",
+                VMState::Trap => "trap",
+                _ => "running",
+            }
+        };
+        
+        Self {
+            output: STANDARD.encode(vm.get_output()),
+            registers: vm.registers.to_vec(),
+            instructions_executed: vm.instructions_executed,
+            halt_reason,
+            error,
+            runtime_ms: runtime.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::Instr;
+    use serde_json::Value;
+
+    /// Run `program` to completion and parse the JSON that --json-output would print
+    fn run_to_json(program: &[Instr]) -> Value {
+        let mut vm = VM::for_test(program);
+        let result = vm.run();
+        let run_result = RunResult::from_vm(&mut vm, Duration::ZERO, result.err());
+        serde_json::from_str(&serde_json::to_string(&run_result).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn output_round_trips_through_base64() {
+        // LI R5, c; STORE R5, R0, R0 for each byte, then HALT
+        let mut program = Vec::new();
+        for byte in [b'H', b'i', 0xC8] {
+            program.push(Instr::new(0x0E, 0, 5, byte as u16, 0));
+            program.push(Instr::new(0x12, 0, 5, 0, 0));
+        }
+        program.push(Instr::new(0x00, 0, 0, 0, 0));
+        
+        let json = run_to_json(&program);
+        
+        let output = json["output"].as_str().unwrap();
+        assert_eq!(STANDARD.decode(output).unwrap(), b"Hi\xC8");
+        assert_eq!(json["halt_reason"], "halt");
+        assert_eq!(json["instructions_executed"], 7);
+        assert_eq!(json["registers"].as_array().unwrap().len(), 32);
+        assert_eq!(json["registers"][5], 0xC8);
+        assert!(json["error"].is_null());
+        assert!(json["runtime_ms"].is_number());
+    }
+
+    #[test]
+    fn brk_is_reported_as_trap() {
+        let json = run_to_json(&[Instr::new(0x19, 0, 0, 0, 0), Instr::new(0x00, 0, 0, 0, 0)]);
+        assert_eq!(json["halt_reason"], "trap");
+        assert!(json["error"].is_null());
+    }
+}
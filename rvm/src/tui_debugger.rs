@@ -14,6 +14,7 @@ use ratatui::{
 use ripple_asm::Register;
 use crate::vm::{VM, VMState};
 use crate::settings::DebuggerSettings;
+use crate::debugger_ui::changed_registers;
 
 // Fixed memory columns for navigation (actual display adjusts dynamically)
 pub(crate) const MEMORY_NAV_COLS: usize = 8;
@@ -79,6 +80,7 @@ pub struct TuiDebugger {
     
     // Debugging state
     pub(crate) breakpoints: HashMap<usize, bool>, // address -> enabled
+    pub(crate) temp_breakpoint: Option<usize>, // one-shot stop for :run-to, kept apart from user breakpoints
     pub(crate) selected_breakpoint: usize, // index in sorted breakpoints list
     pub(crate) memory_watches: Vec<MemoryWatch>,
     pub(crate) selected_watch: usize,
@@ -114,7 +116,7 @@ pub struct TuiDebugger {
     pub(crate) execution_history: Vec<usize>,
     max_history: usize,
     
-    // Register highlights (changed registers)
+    // Register highlights (changed registers -> value before the last step)
     pub(crate) register_changes: HashMap<usize, u16>,
     
     // Panel areas for mouse support
@@ -155,6 +157,7 @@ impl TuiDebugger {
             breakpoints_scroll: 0,
             
             breakpoints: HashMap::new(),
+            temp_breakpoint: None,
             selected_breakpoint: 0,
             memory_watches: Vec::new(),
             selected_watch: 0,
@@ -484,6 +487,14 @@ impl TuiDebugger {
         let pcb = vm.registers[Register::Pcb as usize] as usize;
         let addr = pcb * vm.bank_size as usize + pc;
         
+        // A :run-to target is removed as soon as it is hit
+        if self.temp_breakpoint == Some(addr) && matches!(vm.state, VMState::Running) {
+            self.temp_breakpoint = None;
+            vm.state = VMState::Breakpoint;
+            self.status_message = Some(format!("Reached {addr:#06X}"));
+            return;
+        }
+        
         // Only stop at enabled breakpoints if we're in Running state (not already at a breakpoint)
         if let Some(&enabled) = self.breakpoints.get(&addr) {
             if enabled && matches!(vm.state, VMState::Running) {
@@ -513,11 +524,10 @@ impl TuiDebugger {
         // Step the VM
         let _ = vm.step();
         
-        // Track register changes
-        for i in 0..18 {
-            if old_registers[i] != vm.registers[i] {
-                self.register_changes.insert(i, old_registers[i]);
-            }
+        // Track register changes from this step only, keeping the previous values
+        self.register_changes.clear();
+        for i in changed_registers(&old_registers, &vm.registers) {
+            self.register_changes.insert(i, old_registers[i]);
         }
         
         // Auto-scroll disassembly to keep PC visible
@@ -555,6 +565,29 @@ impl TuiDebugger {
         }
     }
     
+    /// Continue until PC reaches `target`, without touching the user's breakpoints.
+    /// Stops early at any enabled breakpoint; the temporary one is dropped either way.
+    pub(crate) fn run_to(&mut self, vm: &mut VM, target: usize) {
+        if !matches!(vm.state, VMState::Running | VMState::Breakpoint) {
+            self.status_message = Some("Program is not running".to_string());
+            return;
+        }
+        
+        self.temp_breakpoint = Some(target);
+        vm.state = VMState::Running;
+        
+        // Step off the current instruction so a target at PC means "next time we get here"
+        self.step_vm_no_break_check(vm);
+        if matches!(vm.state, VMState::Running) {
+            self.run_until_break(vm);
+        }
+        
+        // Stopped somewhere else (halt, error, user breakpoint): forget the target
+        if self.temp_breakpoint.take().is_some() && matches!(vm.state, VMState::Halted) {
+            self.status_message = Some(format!("Halted before reaching {target:#06X}"));
+        }
+    }
+    
     fn handle_mouse_event(&mut self, mouse: MouseEvent, vm: &mut VM) {
         // Only handle left button clicks in normal mode
         if self.mode != DebuggerMode::Normal {
@@ -693,4 +726,69 @@ impl TuiDebugger {
         }
     }
 
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::{Instr, TestVm};
+
+    /// Five LIs into R5 followed by HALT at instruction 5
+    fn straight_line_vm() -> TestVm {
+        let mut program: Vec<Instr> = (0..5).map(|i| Instr::new(0x0E, 0, 5, i, 0)).collect();
+        program.push(Instr::new(0x00, 0, 0, 0, 0));
+        let mut vm = VM::for_test(&program);
+        vm.debug_mode = true;
+        vm
+    }
+
+    #[test]
+    fn run_to_stops_at_target() {
+        let mut vm = straight_line_vm();
+        let mut tui = TuiDebugger::new();
+        
+        tui.run_to(&mut vm, 3);
+        
+        assert!(matches!(vm.state, VMState::Breakpoint));
+        assert_eq!(vm.registers[Register::Pc as usize], 3);
+        assert_eq!(tui.temp_breakpoint, None);
+    }
+
+    #[test]
+    fn run_to_keeps_coincident_user_breakpoint() {
+        let mut vm = straight_line_vm();
+        let mut tui = TuiDebugger::new();
+        tui.breakpoints.insert(3, true);
+        
+        tui.run_to(&mut vm, 3);
+        
+        assert_eq!(vm.registers[Register::Pc as usize], 3);
+        assert_eq!(tui.breakpoints.get(&3), Some(&true));
+        assert_eq!(tui.temp_breakpoint, None);
+    }
+
+    #[test]
+    fn run_to_past_halt_drops_target() {
+        let mut vm = straight_line_vm();
+        let mut tui = TuiDebugger::new();
+        
+        tui.run_to(&mut vm, 100);
+        
+        assert!(matches!(vm.state, VMState::Halted));
+        assert_eq!(tui.temp_breakpoint, None);
+    }
+
+    #[test]
+    fn run_to_without_address_shows_usage() {
+        let mut vm = straight_line_vm();
+        let mut tui = TuiDebugger::new();
+        
+        tui.execute_command("run-to", &mut vm);
+        assert!(tui.status_message.as_deref().is_some_and(|m| m.starts_with("Usage")));
+        tui.status_message = None;
+        tui.execute_command("run-to zz", &mut vm);
+        assert!(tui.status_message.as_deref().is_some_and(|m| m.starts_with("Usage")));
+        
+        assert_eq!(vm.registers[Register::Pc as usize], 0);
+    }
+}
@@ -8,6 +8,9 @@ impl VM {
         // R0 always reads as 0
         self.registers[Register::R0 as usize] = 0;
         
+        // Signed overflow detected by an arithmetic instruction (--trap-overflow)
+        let mut overflow: Option<(&str, char, u16, u16)> = None;
+        
         match instr.opcode {
             0x00 => {
                 // NOP or HALT (HALT is NOP with all operands 0)
@@ -24,7 +27,11 @@ impl VM {
                 let rs = instr.word2 as usize;
                 let rt = instr.word3 as usize;
                 if rd < 32 && rs < 32 && rt < 32 {
-                    self.registers[rd] = self.registers[rs].wrapping_add(self.registers[rt]);
+                    let (a, b) = (self.registers[rs], self.registers[rt]);
+                    self.registers[rd] = a.wrapping_add(b);
+                    if (a as i16).checked_add(b as i16).is_none() {
+                        overflow = Some(("ADD", '+', a, b));
+                    }
                 }
             },
             0x02 => { // SUB
@@ -32,7 +39,11 @@ impl VM {
                 let rs = instr.word2 as usize;
                 let rt = instr.word3 as usize;
                 if rd < 32 && rs < 32 && rt < 32 {
-                    self.registers[rd] = self.registers[rs].wrapping_sub(self.registers[rt]);
+                    let (a, b) = (self.registers[rs], self.registers[rt]);
+                    self.registers[rd] = a.wrapping_sub(b);
+                    if (a as i16).checked_sub(b as i16).is_none() {
+                        overflow = Some(("SUB", '-', a, b));
+                    }
                 }
             },
             0x03 => { // AND
@@ -102,7 +113,11 @@ impl VM {
                 let rs = instr.word2 as usize;
                 let imm = instr.word3;
                 if rd < 32 && rs < 32 {
-                    self.registers[rd] = self.registers[rs].wrapping_add(imm);
+                    let a = self.registers[rs];
+                    self.registers[rd] = a.wrapping_add(imm);
+                    if (a as i16).checked_add(imm as i16).is_none() {
+                        overflow = Some(("ADDI", '+', a, imm));
+                    }
                 }
             },
             0x0B => { // ANDI
@@ -320,17 +335,22 @@ impl VM {
                 }
             },
             
-            0x19 => { // BRK - debugger breakpoint
+            0x19 => { // BRK - debugger breakpoint / trap
+                // Tell BRK apart from user breakpoints and HALT when reporting
+                self.trap_message = Some(format!(
+                    "BRK at {:04X}:{:04X}",
+                    self.registers[Register::Pcb as usize], self.registers[Register::Pc as usize]
+                ));
+                
                 if self.debug_mode {
                     // In debug mode, just pause execution
                     self.state = VMState::Breakpoint;
                     // Don't print here - the debugger_ui will handle it
                 } else {
-                    // In normal mode, dump state and halt
+                    // In normal mode, dump state and stop in the trap state
                     self.dump_vm_state();
                     
-                    // Halt execution
-                    self.state = VMState::Halted;
+                    self.state = VMState::Trap;
                     return Ok(());
                 }
             },
@@ -341,7 +361,11 @@ impl VM {
                 let rs = instr.word2 as usize;
                 let rt = instr.word3 as usize;
                 if rd < 32 && rs < 32 && rt < 32 {
-                    self.registers[rd] = self.registers[rs].wrapping_mul(self.registers[rt]);
+                    let (a, b) = (self.registers[rs], self.registers[rt]);
+                    self.registers[rd] = a.wrapping_mul(b);
+                    if (a as i16).checked_mul(b as i16).is_none() {
+                        overflow = Some(("MUL", '*', a, b));
+                    }
                 }
             },
             0x1B => { // DIV
@@ -373,7 +397,11 @@ impl VM {
                 let rs = instr.word2 as usize;
                 let imm = instr.word3;
                 if rd < 32 && rs < 32 {
-                    self.registers[rd] = self.registers[rs].wrapping_mul(imm);
+                    let a = self.registers[rs];
+                    self.registers[rd] = a.wrapping_mul(imm);
+                    if (a as i16).checked_mul(imm as i16).is_none() {
+                        overflow = Some(("MULI", '*', a, imm));
+                    }
                 }
             },
             0x1E => { // DIVI
@@ -414,9 +442,34 @@ impl VM {
             self.output_ready = true;
         }
         
+        if let Some((mnemonic, op, a, b)) = overflow {
+            if self.trap_overflow {
+                return self.raise_overflow_trap(mnemonic, op, a, b);
+            }
+        }
+        
         Ok(())
     }
     
+    /// Report a signed overflow: pause in debug mode, otherwise stop with an error.
+    /// The wrapped result has already been written, so continuing behaves like the hardware.
+    fn raise_overflow_trap(&mut self, mnemonic: &str, op: char, a: u16, b: u16) -> Result<(), String> {
+        let message = format!(
+            "Overflow trap at {:04X}:{:04X}: {} {} {} {} overflows 16-bit signed range",
+            self.registers[Register::Pcb as usize], self.registers[Register::Pc as usize],
+            mnemonic, a as i16, op, b as i16
+        );
+        
+        if self.debug_mode {
+            self.state = VMState::Breakpoint;
+            self.trap_message = Some(message);
+            Ok(())
+        } else {
+            self.state = VMState::Error(message.clone());
+            Err(message)
+        }
+    }
+    
     fn dump_vm_state(&self) {
         eprintln!("\n=== BRK: VM State Dump ===");
         eprintln!("PC: {} (bank: {})", self.registers[Register::Pc as usize], self.registers[Register::Pcb as usize]);
@@ -549,4 +602,61 @@ impl VM {
             _ => eprintln!("UNKNOWN 0x{:02X}", instr.opcode),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vm::{Instr, TestVm, VM, VMState};
+
+    /// LI R5, 0x7FFF; LI R6, 1; ADD R7, R5, R6; HALT
+    fn overflowing_add() -> TestVm {
+        VM::for_test(&[
+            Instr::new(0x0E, 0, 5, 0x7FFF, 0),
+            Instr::new(0x0E, 0, 6, 1, 0),
+            Instr::new(0x01, 0, 7, 5, 6),
+            Instr::new(0x00, 0, 0, 0, 0),
+        ])
+    }
+
+    #[test]
+    fn add_overflow_wraps_without_flag() {
+        let mut vm = overflowing_add();
+        vm.run().unwrap();
+        assert!(matches!(vm.state, VMState::Halted));
+        assert_eq!(vm.registers[7], 0x8000);
+    }
+
+    #[test]
+    fn add_overflow_traps_with_flag() {
+        let mut vm = overflowing_add();
+        vm.trap_overflow = true;
+        let err = vm.run().unwrap_err();
+        assert!(err.contains("ADD 32767 + 1"), "{err}");
+        assert!(matches!(vm.state, VMState::Error(_)));
+    }
+
+    #[test]
+    fn add_overflow_pauses_in_debug_mode() {
+        let mut vm = overflowing_add();
+        vm.trap_overflow = true;
+        vm.debug_mode = true;
+        vm.run().unwrap();
+        assert!(matches!(vm.state, VMState::Breakpoint));
+        assert!(vm.trap_message.is_some());
+        // The wrapped result is still written
+        assert_eq!(vm.registers[7], 0x8000);
+    }
+
+    #[test]
+    fn addi_decrement_does_not_trap() {
+        // ADDI R5, R5, 0xFFFF is R5 - 1, not an overflow
+        let mut vm = VM::for_test(&[
+            Instr::new(0x0E, 0, 5, 5, 0),
+            Instr::new(0x0A, 0, 5, 5, 0xFFFF),
+            Instr::new(0x00, 0, 0, 0, 0),
+        ]);
+        vm.trap_overflow = true;
+        vm.run().unwrap();
+        assert_eq!(vm.registers[5], 4);
+    }
+}
@@ -1,7 +1,190 @@
 use crate::constants::*;
 use super::VM;
 
+/// Called when a program loads from a device address
+pub type MmioReadHandler = Box<dyn FnMut(&mut VM, usize) -> u16 + Send>;
+
+/// Called when a program stores to a device address
+pub type MmioWriteHandler = Box<dyn FnMut(&mut VM, usize, u16) + Send>;
+
+/// A device occupying one word of the bank 0 MMIO header.
+/// Handlers are closures, so a device can carry its own state.
+pub struct MmioDevice {
+    pub name: &'static str,
+    pub read: Option<MmioReadHandler>,   // None = write-only, loads return 0
+    pub write: Option<MmioWriteHandler>, // None = read-only, stores are ignored
+}
+
+impl MmioDevice {
+    pub fn new(name: &'static str) -> Self {
+        Self { name, read: None, write: None }
+    }
+    
+    pub fn on_read(mut self, handler: impl FnMut(&mut VM, usize) -> u16 + Send + 'static) -> Self {
+        self.read = Some(Box::new(handler));
+        self
+    }
+    
+    pub fn on_write(mut self, handler: impl FnMut(&mut VM, usize, u16) + Send + 'static) -> Self {
+        self.write = Some(Box::new(handler));
+        self
+    }
+}
+
+/// How TTY_OUT bytes are written to stdout
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputMode {
+    /// Write each byte unchanged
+    #[default]
+    Raw,
+    /// Decode bytes as UTF-8, replacing invalid sequences with U+FFFD
+    Utf8,
+}
+
 impl VM {
+    /// Map a device into the MMIO header, replacing whatever handled that address before
+    pub fn register_mmio_device(&mut self, addr: usize, device: MmioDevice) -> Result<(), String> {
+        if addr >= TEXT40_BASE_WORD {
+            return Err(format!("MMIO address {addr} is outside the header (0..{TEXT40_BASE_WORD})"));
+        }
+        self.mmio_devices.insert(addr, device);
+        Ok(())
+    }
+    
+    /// Devices every VM starts with
+    pub(super) fn register_default_devices(&mut self) {
+        let devices = [
+            (HDR_TTY_OUT, MmioDevice::new("TTY_OUT").on_write(VM::tty_out_write)),
+            (HDR_TTY_IN_POP, MmioDevice::new("TTY_IN_POP").on_read(VM::tty_in_pop_read)),
+            (HDR_RNG, MmioDevice::new("RNG").on_read(VM::rng_read)),
+            (HDR_FB_BASE, MmioDevice::new("FB_BASE").on_read(VM::fb_base_read)),
+        ];
+        for (addr, device) in devices {
+            self.register_mmio_device(addr, device).expect("built-in devices live in the MMIO header");
+        }
+    }
+    
+    fn tty_out_write(&mut self, _addr: usize, value: u16) {
+        // Output low byte to stdout
+        let byte = (value & 0xFF) as u8;
+        
+        // Print immediately to stdout for real-time effect
+        if self.echo_output {
+            use std::io::{self, Write};
+            
+            let bytes = self.tty_out_bytes(byte);
+            let _ = io::stdout().write_all(&bytes);
+            let _ = io::stdout().flush();
+        }
+        
+        // Also store in buffer for compatibility
+        self.output_buffer.push_back(byte);
+        self.output_ready = false;
+        // Simulate output delay (will be set ready in next cycle)
+    }
+    
+    /// Bytes to echo for one TTY_OUT store, after UTF-8 decoding and newline translation
+    fn tty_out_bytes(&mut self, byte: u8) -> Vec<u8> {
+        let bytes = match self.output_mode {
+            OutputMode::Raw => vec![byte],
+            OutputMode::Utf8 => self.decode_utf8_output(byte).into_bytes(),
+        };
+        
+        // The terminal is in raw mode while TTY input is enabled, so every
+        // newline needs an explicit carriage return
+        if !self.tty_input_enabled {
+            return bytes;
+        }
+        let mut translated = Vec::with_capacity(bytes.len() + 1);
+        for b in bytes {
+            if b == b'\n' {
+                translated.push(b'\r');
+            }
+            translated.push(b);
+        }
+        translated
+    }
+    
+    /// Emit U+FFFD for a UTF-8 sequence left incomplete when the program stops or the VM resets
+    pub(super) fn flush_utf8_output(&mut self) {
+        let text = self.finish_utf8_output();
+        if self.echo_output && !text.is_empty() {
+            use std::io::{self, Write};
+            
+            let _ = io::stdout().write_all(text.as_bytes());
+            let _ = io::stdout().flush();
+        }
+    }
+    
+    /// Drain the UTF-8 decoder, returning U+FFFD if it was waiting for more bytes
+    fn finish_utf8_output(&mut self) -> String {
+        if self.utf8_pending.is_empty() {
+            return String::new();
+        }
+        self.utf8_pending.clear();
+        char::REPLACEMENT_CHARACTER.to_string()
+    }
+    
+    /// Feed one output byte to the UTF-8 decoder and return whatever text it completes
+    fn decode_utf8_output(&mut self, byte: u8) -> String {
+        self.utf8_pending.push(byte);
+        let mut decoded = String::new();
+        
+        loop {
+            match std::str::from_utf8(&self.utf8_pending) {
+                Ok(text) => {
+                    decoded.push_str(text);
+                    self.utf8_pending.clear();
+                    break;
+                }
+                Err(e) => {
+                    let valid = e.valid_up_to();
+                    decoded.push_str(&String::from_utf8_lossy(&self.utf8_pending[..valid]));
+                    match e.error_len() {
+                        Some(len) => {
+                            decoded.push(char::REPLACEMENT_CHARACTER);
+                            self.utf8_pending.drain(..valid + len);
+                        }
+                        None => {
+                            // Incomplete sequence, wait for the remaining bytes
+                            self.utf8_pending.drain(..valid);
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        
+        decoded
+    }
+    
+    fn tty_in_pop_read(&mut self, addr: usize) -> u16 {
+        // Enable TTY input mode on first access
+        if !self.tty_input_enabled && !self.debug_mode && !self.verbose {
+            self.enable_tty_input();
+        }
+        
+        // Pop a byte from input buffer, 0 if it is empty
+        let value = self.input_buffer.pop_front().map_or(0, |byte| byte as u16);
+        // Store the popped value in memory
+        self.memory[addr] = value;
+        value
+    }
+    
+    fn rng_read(&mut self, addr: usize) -> u16 {
+        let value = self.next_random();
+        // Store the generated value in memory
+        self.memory[addr] = value;
+        value
+    }
+    
+    fn fb_base_read(&mut self, addr: usize) -> u16 {
+        // The RGB565 front buffer starts right after the MMIO header; there is none in other modes
+        let value = if self.display_mode == DISP_RGB565 { TEXT40_BASE_WORD as u16 } else { 0 };
+        self.memory[addr] = value;
+        value
+    }
+    
     /// Handle MMIO reads for special addresses in bank 0
     pub(super) fn handle_mmio_read(&mut self, addr: usize) -> Option<u16> {
         // Only handle bank 0 MMIO addresses
@@ -9,29 +192,21 @@ impl VM {
             return None; // Regular memory access for VRAM and beyond
         }
         
+        // Registered devices take precedence over the built-in registers. The device is
+        // taken out of the table while its handler runs so the handler can borrow the VM.
+        if let Some(mut device) = self.mmio_devices.remove(&addr) {
+            let value = device.read.as_mut().map_or(0, |read| read(self, addr));
+            log::debug!("MMIO {} read -> {:#06x}", device.name, value);
+            self.mmio_devices.entry(addr).or_insert(device);
+            return Some(value);
+        }
+        
         match addr {
-            HDR_TTY_OUT => Some(0), // Write-only, return 0
             HDR_TTY_STATUS => {
                 let value = if self.output_ready { TTY_READY } else { 0 };
                 self.memory[HDR_TTY_STATUS] = value;
                 Some(value)
             },
-            HDR_TTY_IN_POP => {
-                // Enable TTY input mode on first access
-                if !self.tty_input_enabled && !self.debug_mode && !self.verbose {
-                    self.enable_tty_input();
-                }
-                
-                // Pop a byte from input buffer
-                let value = if let Some(byte) = self.input_buffer.pop_front() {
-                    byte as u16
-                } else {
-                    0 // Return 0 if buffer is empty
-                };
-                // Store the popped value in memory
-                self.memory[HDR_TTY_IN_POP] = value;
-                Some(value)
-            },
             HDR_TTY_IN_STATUS => {
                 // Enable TTY input mode on first access
                 if !self.tty_input_enabled && !self.debug_mode && !self.verbose {
@@ -42,14 +217,6 @@ impl VM {
                 self.memory[HDR_TTY_IN_STATUS] = value;
                 Some(value)
             },
-            HDR_RNG => {
-                // Simple LCG: next = (a * prev + c) mod m
-                self.rng_state = self.rng_state.wrapping_mul(1664525).wrapping_add(1013904223);
-                let value = (self.rng_state >> 16) as u16;
-                // Store the generated value in memory
-                self.memory[HDR_RNG] = value;
-                Some(value) // Return the value
-            },
             HDR_RNG_SEED => {
                 // Return the low 16 bits of current seed
                 let value = (self.rng_state & 0xFFFF) as u16;
@@ -160,7 +327,7 @@ impl VM {
                     Some(0)  // No storage available
                 }
             },
-            21..=31 => Some(0), // Reserved addresses return 0
+            22..=31 => Some(0), // Reserved addresses return 0
             _ => None, // Not an MMIO address
         }
     }
@@ -172,33 +339,19 @@ impl VM {
             return false; // Regular memory write for VRAM and beyond
         }
         
+        // Registered devices take precedence over the built-in registers
+        if let Some(mut device) = self.mmio_devices.remove(&addr) {
+            log::debug!("MMIO {} write {:#06x}", device.name, value);
+            if let Some(write) = device.write.as_mut() {
+                write(self, addr, value);
+            }
+            self.mmio_devices.entry(addr).or_insert(device);
+            return true;
+        }
+        
         match addr {
-            HDR_TTY_OUT => {
-                // Output low byte to stdout
-                let byte = (value & 0xFF) as u8;
-                
-                // Print immediately to stdout for real-time effect
-                use std::io::{self, Write};
-                
-                // If we're in raw mode and outputting a newline, also output carriage return
-                if self.tty_input_enabled && byte == b'\n' {
-                    // Output \r\n for proper line ending in raw mode
-                    let _ = io::stdout().write_all(b"\r\n");
-                } else {
-                    let _ = io::stdout().write_all(&[byte]);
-                }
-                let _ = io::stdout().flush();
-                
-                // Also store in buffer for compatibility
-                self.output_buffer.push_back(byte);
-                self.output_ready = false;
-                // Simulate output delay (will be set ready in next cycle)
-                true
-            },
             HDR_TTY_STATUS => true, // Read-only, ignore write
-            HDR_TTY_IN_POP => true, // Read-only, ignore write
             HDR_TTY_IN_STATUS => true, // Read-only, ignore write
-            HDR_RNG => true, // Read-only, ignore write
             HDR_RNG_SEED => {
                 // Set the low 16 bits of the RNG seed
                 // Keep high 16 bits unchanged to maintain some state
@@ -287,8 +440,159 @@ impl VM {
                 }
                 true
             },
-            21..=31 => true, // Reserved addresses, ignore writes
+            22..=31 => true, // Reserved addresses, ignore writes
             _ => false, // Not an MMIO address
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use crate::constants::*;
+    use crate::vm::{Instr, MmioDevice, OutputMode, VM};
+
+    const HALT: Instr = Instr { opcode: 0x00, word0: 0, word1: 0, word2: 0, word3: 0 };
+
+    fn decode(vm: &mut VM, bytes: &[u8]) -> String {
+        bytes.iter().map(|&b| vm.decode_utf8_output(b)).collect()
+    }
+
+    #[test]
+    fn utf8_multibyte_sequence_spans_stores() {
+        let mut vm = VM::for_test(&[HALT]);
+        assert_eq!(vm.decode_utf8_output(0xE2), "");
+        assert_eq!(vm.decode_utf8_output(0x82), "");
+        assert_eq!(vm.decode_utf8_output(0xAC), "\u{20AC}");
+        assert_eq!(decode(&mut vm, "héllo".as_bytes()), "héllo");
+    }
+
+    #[test]
+    fn utf8_invalid_bytes_become_replacement_chars() {
+        let mut vm = VM::for_test(&[HALT]);
+        assert_eq!(decode(&mut vm, &[0xFF, b'A']), "\u{FFFD}A");
+        // A lead byte cut short by an ASCII byte
+        assert_eq!(decode(&mut vm, &[0xC3, b'B']), "\u{FFFD}B");
+    }
+
+    #[test]
+    fn utf8_incomplete_sequence_is_flushed_on_halt() {
+        let mut vm = VM::for_test(&[HALT]);
+        assert_eq!(vm.decode_utf8_output(0xE2), "");
+        assert_eq!(vm.finish_utf8_output(), "\u{FFFD}");
+        assert_eq!(vm.finish_utf8_output(), "");
+        
+        // HALT drains the decoder
+        vm.decode_utf8_output(0xE2);
+        vm.output_mode = OutputMode::Utf8;
+        vm.echo_output = false;
+        vm.run().unwrap();
+        assert!(vm.utf8_pending.is_empty());
+    }
+
+    #[test]
+    fn utf8_incomplete_sequence_is_flushed_on_brk() {
+        let mut vm = VM::for_test(&[Instr::new(0x19, 0, 0, 0, 0), HALT]);
+        vm.output_mode = OutputMode::Utf8;
+        vm.decode_utf8_output(0xE2);
+        vm.run().unwrap();
+        assert!(matches!(vm.state, crate::vm::VMState::Trap));
+        assert!(vm.utf8_pending.is_empty());
+    }
+
+    #[test]
+    fn tty_input_translates_every_newline() {
+        let mut vm = VM::for_test(&[HALT]);
+        vm.tty_input_enabled = true;
+        assert_eq!(vm.tty_out_bytes(b'\n'), b"\r\n");
+        
+        // A cut-short sequence decodes to U+FFFD followed by the newline
+        vm.output_mode = OutputMode::Utf8;
+        assert_eq!(vm.tty_out_bytes(0xC3), b"");
+        assert_eq!(vm.tty_out_bytes(b'\n'), "\u{FFFD}\r\n".as_bytes());
+        
+        vm.tty_input_enabled = false;
+        assert_eq!(vm.tty_out_bytes(b'\n'), b"\n");
+    }
+
+    #[test]
+    fn store_to_tty_out_emits_low_byte() {
+        // LI R5, 0x141; STORE R5, R0, R0
+        let mut vm = VM::for_test(&[Instr::new(0x0E, 0, 5, 0x141, 0), Instr::new(0x12, 0, 5, 0, 0), HALT]);
+        vm.run().unwrap();
+        assert_eq!(vm.get_output(), b"A");
+    }
+
+    #[test]
+    fn store_reaches_registered_device() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&received);
+        
+        // LI R5, 0xBEEF; LI R6, 22; STORE R5, R0, R6
+        let mut vm = VM::for_test(&[
+            Instr::new(0x0E, 0, 5, 0xBEEF, 0),
+            Instr::new(0x0E, 0, 6, 22, 0),
+            Instr::new(0x12, 0, 5, 0, 6),
+            HALT,
+        ]);
+        vm.register_mmio_device(22, MmioDevice::new("SINK").on_write(move |_, addr, value| {
+            sink.lock().unwrap().push((addr, value));
+        })).unwrap();
+        vm.run().unwrap();
+        
+        assert_eq!(*received.lock().unwrap(), [(22, 0xBEEF)]);
+        // The store was consumed by the device, not written to memory
+        assert_eq!(vm.memory[22], 0);
+    }
+
+    #[test]
+    fn device_keeps_its_own_state() {
+        // LI R6, 23; LOAD R5, R0, R6; LOAD R7, R0, R6
+        let mut vm = VM::for_test(&[
+            Instr::new(0x0E, 0, 6, 23, 0),
+            Instr::new(0x11, 0, 5, 0, 6),
+            Instr::new(0x11, 0, 7, 0, 6),
+            HALT,
+        ]);
+        let mut count = 0;
+        vm.register_mmio_device(23, MmioDevice::new("COUNTER").on_read(move |_, _| {
+            count += 1;
+            count
+        })).unwrap();
+        vm.run().unwrap();
+        
+        assert_eq!((vm.registers[5], vm.registers[7]), (1, 2));
+    }
+
+    #[test]
+    fn devices_must_live_in_the_header() {
+        let mut vm = VM::for_test(&[HALT]);
+        assert!(vm.register_mmio_device(TEXT40_BASE_WORD, MmioDevice::new("VRAM")).is_err());
+    }
+
+    #[test]
+    fn fb_base_is_zero_outside_rgb565_mode() {
+        // LI R6, HDR_FB_BASE; LOAD R5, R0, R6
+        let mut vm = VM::for_test(&[
+            Instr::new(0x0E, 0, 6, HDR_FB_BASE as u16, 0),
+            Instr::new(0x11, 0, 5, 0, 6),
+            HALT,
+        ]);
+        vm.run().unwrap();
+        assert_eq!(vm.registers[5], 0);
+    }
+
+    #[test]
+    fn fb_base_reports_front_buffer_in_rgb565_mode() {
+        // LI R6, HDR_FB_BASE; LOAD R5, R0, R6
+        let mut vm = VM::for_test(&[
+            Instr::new(0x0E, 0, 6, HDR_FB_BASE as u16, 0),
+            Instr::new(0x11, 0, 5, 0, 6),
+            HALT,
+        ]);
+        // Set the mode directly; writing HDR_DISP_MODE would open a window
+        vm.display_mode = DISP_RGB565;
+        vm.run().unwrap();
+        assert_eq!(vm.registers[5], TEXT40_BASE_WORD as u16);
+    }
+}
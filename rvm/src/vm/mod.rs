@@ -7,9 +7,13 @@ mod display;
 mod terminal;
 mod execution;
 mod storage;
+mod regions;
+mod pacing;
 
 pub use instruction::Instr;
 pub use state::{VMState, KeyboardState};
+pub use regions::MemoryRegion;
+pub use mmio::{MmioDevice, OutputMode};
 pub use terminal::install_terminal_cleanup_hook;
 
 use std::collections::{VecDeque, HashMap};
@@ -41,6 +45,12 @@ pub struct VM {
     // Verbose mode flag
     pub verbose: bool,
     
+    // Trap on signed overflow in ADD/SUB/ADDI/MUL/MULI instead of wrapping silently
+    pub trap_overflow: bool,
+    
+    // Description of the trap that paused execution, if any
+    pub trap_message: Option<String>,
+    
     // Do not increment PC flag (set by jump/branch instructions)
     skip_pc_increment: bool,
     
@@ -48,13 +58,25 @@ pub struct VM {
     pub output_buffer: VecDeque<u8>,
     output_ready: bool,
     
+    // Echo TTY output to stdout as it is produced (disabled for --json-output)
+    pub echo_output: bool,
+    
+    // Encoding used when echoing TTY output
+    pub output_mode: OutputMode,
+    
+    // Bytes of a partially received UTF-8 sequence (OutputMode::Utf8)
+    utf8_pending: Vec<u8>,
+    
+    // Number of instructions executed since load/reset
+    pub instructions_executed: u64,
+    
     // Input buffer for TTY_IN
     pub input_buffer: VecDeque<u8>,
     
     // TTY input mode state
     tty_input_enabled: bool,
     
-    // RNG state (simple LCG)
+    // RNG state (LCG, see RNG_* constants)
     rng_state: u32,
     
     // Display state
@@ -76,6 +98,12 @@ pub struct VM {
     // Debug information: maps instruction indices to function names
     pub debug_symbols: HashMap<usize, String>,
     
+    // Flat address range the data section was loaded into
+    data_section: std::ops::Range<usize>,
+    
+    // Memory-mapped devices in the bank 0 header, keyed by word address
+    mmio_devices: HashMap<usize, MmioDevice>,
+    
     // Storage subsystem
     storage: Option<Storage>,
 }
@@ -110,7 +138,7 @@ impl VM {
             }
         };
         
-        VM {
+        let mut vm = VM {
             instructions: Vec::new(),
             memory: vec![0; memory_size],
             registers: [0; 32],
@@ -118,12 +146,18 @@ impl VM {
             bank_size,
             debug_mode: false,
             verbose: false,
+            trap_overflow: false,
+            trap_message: None,
             skip_pc_increment: false,
             output_buffer: VecDeque::new(),
             output_ready: true,
+            echo_output: true,
+            output_mode: OutputMode::Raw,
+            utf8_pending: Vec::new(),
+            instructions_executed: 0,
             input_buffer: VecDeque::new(),
             tty_input_enabled: false,
-            rng_state: 0x12345678,  // Fixed seed for reproducibility
+            rng_state: RNG_DEFAULT_SEED,  // Fixed seed for reproducibility
             display_mode: DISP_OFF,
             display_enabled: false,
             display_flush_done: true,
@@ -133,8 +167,12 @@ impl VM {
             rgb565_display: None,
             display_resolution: 0,
             debug_symbols: HashMap::new(),
+            data_section: 0..0,
+            mmio_devices: HashMap::new(),
             storage,
-        }
+        };
+        vm.register_default_devices();
+        vm
     }
     
     #[allow(dead_code)]
@@ -146,6 +184,18 @@ impl VM {
         self.rng_state = seed;
     }
     
+    /// Advance an RNG state by one step and return the value HDR_RNG would read.
+    /// Stable across versions so seeded runs are reproducible.
+    pub fn rng_step(state: &mut u32) -> u16 {
+        *state = state.wrapping_mul(RNG_MULTIPLIER).wrapping_add(RNG_INCREMENT);
+        (*state >> 16) as u16
+    }
+    
+    /// Produce the next value from the VM's RNG
+    pub fn next_random(&mut self) -> u16 {
+        Self::rng_step(&mut self.rng_state)
+    }
+    
     pub fn load_binary(&mut self, binary: &[u8]) -> Result<(), String> {
         // Check magic number
         if binary.len() < 5 || &binary[0..5] != MAGIC_RLINK {
@@ -307,6 +357,7 @@ impl VM {
             }
         }
         pos += data_size;
+        self.data_section = data_offset..(data_offset + data_size).min(self.memory.len());
         
         // Try to read debug section if present
         if pos + 5 <= binary.len() && &binary[pos..pos + 5] == b"DEBUG" {
@@ -374,7 +425,7 @@ impl VM {
     pub fn step(&mut self) -> Result<(), String> {
         match self.state {
             VMState::Running => {},
-            VMState::Halted => return Ok(()),
+            VMState::Halted | VMState::Trap => return Ok(()),
             VMState::Breakpoint => {
                 // In debug mode at breakpoint, allow single stepping
                 // State will be reset to Running by the debugger_ui
@@ -399,11 +450,13 @@ impl VM {
         if instr_idx >= self.instructions.len() {
             self.state = VMState::Error(format!("PC out of bounds: bank={}, offset={}, idx={}, total_instructions={}", 
                                                pcb, pc, instr_idx, self.instructions.len()));
+            self.flush_utf8_output();
             return Err(format!("PC out of bounds: idx={} >= len={}", instr_idx, self.instructions.len()));
         }
         
         let instr = self.instructions[instr_idx];
         self.skip_pc_increment = false;
+        self.trap_message = None;
         
         // Print instruction in verbose mode
         if self.verbose {
@@ -411,8 +464,13 @@ impl VM {
             self.print_instruction(&instr);
         }
         
-        // Execute instruction
-        self.execute_instruction(instr)?;
+        // Execute instruction, draining partial UTF-8 output if the program stopped
+        let result = self.execute_instruction(instr);
+        if result.is_err() || matches!(self.state, VMState::Halted | VMState::Trap | VMState::Error(_)) {
+            self.flush_utf8_output();
+        }
+        result?;
+        self.instructions_executed += 1;
         
         // Increment PC unless instruction set the skip flag
         if !self.skip_pc_increment {
@@ -481,8 +539,11 @@ impl VM {
         // Reset state to running (ready to execute)
         self.state = VMState::Running;
         self.skip_pc_increment = false;
+        self.instructions_executed = 0;
+        self.trap_message = None;
         
         // Clear I/O buffers
+        self.flush_utf8_output();
         self.output_buffer.clear();
         self.input_buffer.clear();
         self.output_ready = true;
@@ -536,4 +597,115 @@ impl Drop for VM {
             storage.flush();
         }
     }
-}
\ No newline at end of file
+}
+
+/// A VM for unit tests whose disk image is deleted along with it
+#[cfg(test)]
+pub(crate) struct TestVm {
+    // Dropped first, so storage is flushed before its directory is removed
+    vm: VM,
+    _disk_dir: tempfile::TempDir,
+}
+
+#[cfg(test)]
+impl std::ops::Deref for TestVm {
+    type Target = VM;
+    
+    fn deref(&self) -> &VM {
+        &self.vm
+    }
+}
+
+#[cfg(test)]
+impl std::ops::DerefMut for TestVm {
+    fn deref_mut(&mut self) -> &mut VM {
+        &mut self.vm
+    }
+}
+
+#[cfg(test)]
+impl VM {
+    /// A running VM with `instructions` loaded into 4096-word banks, backed by a
+    /// throwaway disk image and with output collected instead of echoed
+    pub(crate) fn for_test(instructions: &[Instr]) -> TestVm {
+        let disk_dir = tempfile::tempdir().expect("temporary directory for the test disk");
+        let mut vm = VM::with_options(4096, 65536, Some(disk_dir.path().join("disk.img")));
+        
+        let mut binary = MAGIC_RLINK.to_vec();
+        binary.extend_from_slice(&4096u16.to_le_bytes());
+        binary.extend_from_slice(&0u32.to_le_bytes());
+        binary.extend_from_slice(&(instructions.len() as u32).to_le_bytes());
+        for instr in instructions {
+            binary.extend_from_slice(&[instr.opcode, instr.word0]);
+            binary.extend_from_slice(&instr.word1.to_le_bytes());
+            binary.extend_from_slice(&instr.word2.to_le_bytes());
+            binary.extend_from_slice(&instr.word3.to_le_bytes());
+        }
+        binary.extend_from_slice(&0u32.to_le_bytes());
+        
+        vm.load_binary(&binary).expect("test program should load");
+        vm.echo_output = false;
+        TestVm { vm, _disk_dir: disk_dir }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequence(vm: &mut VM, len: usize) -> Vec<u16> {
+        (0..len).map(|_| vm.next_random()).collect()
+    }
+
+    #[test]
+    fn default_seed_sequence_is_pinned() {
+        // Changing these values breaks reproducibility of seeded runs
+        let mut vm = VM::for_test(&[]);
+        assert_eq!(sequence(&mut vm, 4), [0x7543, 0xCD30, 0x25DB, 0x4B5C]);
+        
+        let mut state = RNG_DEFAULT_SEED;
+        assert_eq!(VM::rng_step(&mut state), 0x7543);
+    }
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = VM::for_test(&[]);
+        let mut b = VM::for_test(&[]);
+        a.set_rng_seed(42);
+        b.set_rng_seed(42);
+        assert_eq!(sequence(&mut a, 16), sequence(&mut b, 16));
+    }
+
+    #[test]
+    fn different_seeds_diverge() {
+        let mut a = VM::for_test(&[]);
+        let mut b = VM::for_test(&[]);
+        a.set_rng_seed(1);
+        b.set_rng_seed(2);
+        assert_ne!(sequence(&mut a, 16), sequence(&mut b, 16));
+    }
+    
+    #[test]
+    fn brk_outside_debugger_traps() {
+        let mut vm = VM::for_test(&[Instr::new(0x19, 0, 0, 0, 0), Instr::new(0x00, 0, 0, 0, 0)]);
+        vm.run().unwrap();
+        assert!(matches!(vm.state, VMState::Trap));
+        assert_eq!(vm.trap_message.as_deref(), Some("BRK at 0000:0000"));
+    }
+
+    #[test]
+    fn halt_leaves_vm_halted() {
+        let mut vm = VM::for_test(&[Instr::new(0x00, 0, 0, 0, 0)]);
+        vm.run().unwrap();
+        assert!(matches!(vm.state, VMState::Halted));
+        assert!(vm.trap_message.is_none());
+    }
+
+    #[test]
+    fn brk_in_debug_mode_pauses() {
+        let mut vm = VM::for_test(&[Instr::new(0x19, 0, 0, 0, 0), Instr::new(0x00, 0, 0, 0, 0)]);
+        vm.debug_mode = true;
+        vm.run().unwrap();
+        assert!(matches!(vm.state, VMState::Breakpoint));
+    }
+}
@@ -0,0 +1,104 @@
+use std::thread;
+use std::time::{Duration, Instant};
+use super::{VM, VMState};
+
+impl VM {
+    /// Run VM with frequency limiting
+    pub fn run_with_frequency(&mut self, frequency: u64) -> Result<(), String> {
+        // Target 60 FPS for smooth animation
+        const TARGET_FPS: u64 = 60;
+        const NANOS_PER_SECOND: u128 = 1_000_000_000;
+        
+        if frequency == 0 {
+            return Err("Frequency must be greater than zero".to_string());
+        }
+        
+        // Calculate instructions per frame. Below TARGET_FPS Hz a frame would hold
+        // zero instructions, so fall back to pacing every single instruction.
+        // Computed in u128 so THz-range frequencies can't overflow.
+        let instructions_per_frame = (frequency / TARGET_FPS).max(1);
+        let frame_nanos = NANOS_PER_SECOND * instructions_per_frame as u128 / frequency as u128;
+        let frame_duration = Duration::from_nanos(frame_nanos as u64);
+        
+        let mut next_frame_time = Instant::now() + frame_duration;
+        let mut instructions_in_frame = 0;
+        
+        while matches!(self.state, VMState::Running) {
+            // Execute one instruction
+            self.step()?;
+            instructions_in_frame += 1;
+            
+            // Check if we've executed enough instructions for this frame
+            if instructions_in_frame >= instructions_per_frame {
+                wait_until(next_frame_time);
+                
+                // Schedule against the previous deadline to avoid drift, but don't
+                // try to catch up if we've fallen more than a frame behind
+                next_frame_time += frame_duration;
+                let now = Instant::now();
+                if next_frame_time < now {
+                    next_frame_time = now + frame_duration;
+                }
+                instructions_in_frame = 0;
+            }
+            
+            // Stop if we hit a breakpoint in debug mode
+            if matches!(self.state, VMState::Breakpoint) {
+                break;
+            }
+        }
+        
+        Ok(())
+    }
+}
+
+/// Sleep until shortly before the deadline, then spin for the remainder.
+/// `thread::sleep` alone can overshoot by a millisecond or more.
+fn wait_until(deadline: Instant) {
+    const SPIN_THRESHOLD: Duration = Duration::from_millis(1);
+    
+    let now = Instant::now();
+    if deadline > now + SPIN_THRESHOLD {
+        thread::sleep(deadline - now - SPIN_THRESHOLD);
+    }
+    while Instant::now() < deadline {
+        std::hint::spin_loop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::Instr;
+
+    #[test]
+    fn zero_frequency_is_rejected() {
+        let mut vm = VM::for_test(&[Instr::new(0x00, 0, 0, 0, 0)]);
+        assert!(vm.run_with_frequency(0).is_err());
+    }
+
+    #[test]
+    fn huge_frequency_does_not_overflow() {
+        let mut vm = VM::for_test(&[Instr::new(0x00, 0, 0, 0, 0)]);
+        assert!(vm.run_with_frequency(u64::MAX).is_ok());
+        assert!(matches!(vm.state, VMState::Halted));
+    }
+
+    #[test]
+    fn low_frequency_runs_about_ten_instructions_per_second() {
+        // Nine NOPs (ADD R0, R0, R0) and a HALT: ten paced instructions
+        let mut program = vec![Instr::new(0x01, 0, 0, 0, 0); 9];
+        program.push(Instr::new(0x00, 0, 0, 0, 0));
+        let mut vm = VM::for_test(&program);
+        
+        let start = Instant::now();
+        vm.run_with_frequency(10).unwrap();
+        let elapsed = start.elapsed();
+        
+        assert_eq!(vm.instructions_executed, 10);
+        assert!(
+            elapsed >= Duration::from_millis(900) && elapsed < Duration::from_millis(1500),
+            "10 instructions at 10 Hz took {elapsed:?}"
+        );
+    }
+}
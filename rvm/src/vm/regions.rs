@@ -0,0 +1,134 @@
+use std::ops::Range;
+use crate::constants::*;
+use super::VM;
+use ripple_asm::Register;
+
+/// Known regions of data memory, used by the debugger to label addresses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryRegion {
+    Mmio,     // Bank 0 MMIO header (words 0..31)
+    Vram,     // TEXT40 video memory (bank 0, not used as such in RGB565 mode)
+    Stack,    // Live stack: SB bank from offset 0 up to SP
+    Data,     // Data section loaded from the binary
+    Heap,     // Between the end of the data section and the stack base
+    Unmapped, // Anything else (free memory, other banks)
+}
+
+impl MemoryRegion {
+    /// Short fixed-width label for row indicators
+    pub fn label(&self) -> &'static str {
+        match self {
+            MemoryRegion::Mmio => "I/O ",
+            MemoryRegion::Vram => "VRM ",
+            MemoryRegion::Stack => "STK ",
+            MemoryRegion::Data => "DAT ",
+            MemoryRegion::Heap => "HEP ",
+            MemoryRegion::Unmapped => "    ",
+        }
+    }
+}
+
+/// Classify a flat memory address given the bank size, whether the RGB565
+/// framebuffer replaces TEXT40 VRAM, and the current data and stack ranges
+pub fn classify_address(
+    addr: usize,
+    bank_size: usize,
+    rgb565: bool,
+    data: &Range<usize>,
+    stack: &Range<usize>,
+) -> MemoryRegion {
+    // The header and VRAM are bank 0 offsets; with small banks the same flat
+    // addresses belong to later banks
+    let in_bank0 = addr < bank_size;
+    if in_bank0 && addr < TEXT40_BASE_WORD {
+        MemoryRegion::Mmio
+    } else if in_bank0 && addr <= TEXT40_LAST_WORD && !rgb565 {
+        MemoryRegion::Vram
+    } else if stack.contains(&addr) {
+        MemoryRegion::Stack
+    } else if data.contains(&addr) {
+        MemoryRegion::Data
+    } else if (data.end..stack.start).contains(&addr) {
+        MemoryRegion::Heap
+    } else {
+        MemoryRegion::Unmapped
+    }
+}
+
+impl VM {
+    /// Flat address range of the live stack, following the current SB and SP
+    pub fn stack_range(&self) -> Range<usize> {
+        let sb = self.registers[Register::Sb as usize] as usize;
+        let sp = self.registers[Register::Sp as usize] as usize;
+        let base = sb * self.bank_size as usize;
+        base..base + sp
+    }
+    
+    /// Region that a flat memory address belongs to
+    pub fn memory_region(&self, addr: usize) -> MemoryRegion {
+        classify_address(
+            addr,
+            self.bank_size as usize,
+            self.display_mode == DISP_RGB565,
+            &self.data_section,
+            &self.stack_range(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BANK: usize = 4096;
+    const DATA: Range<usize> = DATA_SECTION_OFFSET..DATA_SECTION_OFFSET + 100;
+
+    #[test]
+    fn header_and_vram() {
+        let stack = 4096..4196;
+        assert_eq!(classify_address(0, BANK, false, &DATA, &stack), MemoryRegion::Mmio);
+        assert_eq!(classify_address(TEXT40_BASE_WORD - 1, BANK, false, &DATA, &stack), MemoryRegion::Mmio);
+        assert_eq!(classify_address(TEXT40_BASE_WORD, BANK, false, &DATA, &stack), MemoryRegion::Vram);
+        assert_eq!(classify_address(TEXT40_LAST_WORD, BANK, false, &DATA, &stack), MemoryRegion::Vram);
+    }
+
+    #[test]
+    fn data_heap_stack_and_unmapped() {
+        // SB = 1, SP = 100 with 4096-word banks
+        let stack = 4096..4196;
+        assert_eq!(classify_address(DATA.start, BANK, false, &DATA, &stack), MemoryRegion::Data);
+        assert_eq!(classify_address(DATA.end - 1, BANK, false, &DATA, &stack), MemoryRegion::Data);
+        assert_eq!(classify_address(DATA.end, BANK, false, &DATA, &stack), MemoryRegion::Heap);
+        assert_eq!(classify_address(4095, BANK, false, &DATA, &stack), MemoryRegion::Heap);
+        assert_eq!(classify_address(4096, BANK, false, &DATA, &stack), MemoryRegion::Stack);
+        assert_eq!(classify_address(4195, BANK, false, &DATA, &stack), MemoryRegion::Stack);
+        assert_eq!(classify_address(4196, BANK, false, &DATA, &stack), MemoryRegion::Unmapped);
+    }
+
+    #[test]
+    fn stack_wins_over_overlapping_data() {
+        // SB = 0 puts the stack on top of the data section
+        let stack = 0..DATA.start + 10;
+        assert_eq!(classify_address(DATA.start + 5, BANK, false, &DATA, &stack), MemoryRegion::Stack);
+        assert_eq!(classify_address(DATA.start + 10, BANK, false, &DATA, &stack), MemoryRegion::Data);
+        // No room for a heap when the stack starts below the data end
+        assert_eq!(classify_address(DATA.end, BANK, false, &DATA, &stack), MemoryRegion::Unmapped);
+    }
+    #[test]
+    fn small_banks_end_bank0_early() {
+        // With 1024-word banks, flat 1024..1031 are bank 1 words 0..7, not VRAM
+        let stack = 4096..4196;
+        assert_eq!(classify_address(1023, 1024, false, &DATA, &stack), MemoryRegion::Vram);
+        assert_eq!(classify_address(1024, 1024, false, &DATA, &stack), MemoryRegion::Unmapped);
+        assert_eq!(classify_address(1031, 1024, false, &DATA, &stack), MemoryRegion::Unmapped);
+        // A bank smaller than the header cuts the header short too
+        assert_eq!(classify_address(20, 16, false, &DATA, &stack), MemoryRegion::Unmapped);
+    }
+
+    #[test]
+    fn rgb565_has_no_text40_vram() {
+        let stack = 4096..4196;
+        assert_eq!(classify_address(0, BANK, true, &DATA, &stack), MemoryRegion::Mmio);
+        assert_eq!(classify_address(TEXT40_BASE_WORD, BANK, true, &DATA, &stack), MemoryRegion::Unmapped);
+    }
+}
@@ -5,6 +5,7 @@ pub enum VMState {
     Running,
     Halted,
     Breakpoint,  // Hit a BRK instruction in debug mode
+    Trap,        // Hit a BRK instruction outside the debugger (e.g. a failed assert)
     Error(String),
 }
 
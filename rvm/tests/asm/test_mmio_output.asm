@@ -0,0 +1,23 @@
+; Test the MMIO device table: stores to TTY_OUT (bank 0, word 0) emit
+; the low byte of the stored value, and a load from FB_BASE (bank 0,
+; word 21) reads 0 because no RGB565 framebuffer is active
+
+_start:
+    ; Only the low byte reaches the output device
+    LI R5, 0x4F   ; 'O'
+    STORE R5, 0, 0
+    LI R5, 0x014B ; 'K' with junk in the high byte
+    STORE R5, 0, 0
+    
+    ; LOAD takes its bank and address from registers: R0 = bank 0, R6 = 21
+    ; FB_BASE is 0 in the default display mode, so this prints '!'
+    LI R6, 21
+    LOAD R7, R0, R6
+    ADDI R7, R7, 33
+    STORE R7, 0, 0
+    
+    ; Load newline (10) and output it
+    LI R5, 10
+    STORE R5, 0, 0
+    
+    HALT
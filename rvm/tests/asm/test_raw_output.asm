@@ -0,0 +1,10 @@
+; Test that output bytes >= 128 reach stdout unchanged (--output-mode raw,
+; the default) instead of being mangled into '?'
+
+_start:
+    ; Output byte 200 (0xC8)
+    LI R5, 200
+    STORE R5, 0, 0
+    
+    ; Halt the program
+    HALT